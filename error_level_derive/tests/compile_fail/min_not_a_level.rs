@@ -0,0 +1,15 @@
+use error_level::ErrorLevel;
+
+#[derive(Debug, ErrorLevel)]
+pub enum InnerError {
+    #[report(info)]
+    Busted,
+}
+
+#[derive(Debug, ErrorLevel)]
+#[report(min = "not-a-level")]
+pub enum CustomError {
+    Wrapped(InnerError),
+}
+
+fn main() {}
@@ -0,0 +1,15 @@
+use error_level::ErrorLevel;
+
+#[derive(Debug, ErrorLevel)]
+pub enum InnerError {
+    #[report(info)]
+    Busted,
+}
+
+#[derive(Debug, ErrorLevel)]
+#[report(min = "no")]
+pub enum CustomError {
+    Wrapped(InnerError),
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use error_level::ErrorLevel;
+
+#[derive(ErrorLevel)]
+pub union CustomError {
+    a: u32,
+    b: f32,
+}
+
+fn main() {}
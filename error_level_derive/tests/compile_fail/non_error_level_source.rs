@@ -0,0 +1,11 @@
+use error_level::ErrorLevel;
+
+#[derive(Debug)]
+pub struct NotAnError;
+
+#[derive(Debug, ErrorLevel)]
+pub enum CustomError {
+    ErrorA(NotAnError),
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use error_level::ErrorLevel;
+
+#[derive(Debug, ErrorLevel)]
+pub enum CustomError {
+    #[report(critical)]
+    ErrorA,
+}
+
+fn main() {}
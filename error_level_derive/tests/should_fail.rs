@@ -1,39 +1,10 @@
-use error_level::ErrorLevel;
-
-#[derive(Debug)]
-enum ErrorWithoutImpl {
-    Error0,
-    Error1,
-}
-
-#[test]
-fn does_not_implement_ErrorLevel() {
-    
-
-    #[derive(Debug, ErrorLevel)]
-    pub enum CustomError {
-        #[level(Warn)]
-        ErrorA,
-        #[level(Info)]
-        ErrorB,
-        #[level(No)]
-        ErrorC,
-        ErrorD(ErrorWithoutImpl),
-    }
-
-    let a = CustomError::ErrorA;
-    let d = CustomError::ErrorD(ErrorWithoutImpl::Error1);
-}
-
+// Drives the compile-fail fixtures in `tests/compile_fail/`: each one is
+// expected to fail to build, and `trybuild` checks that without us having to
+// spawn a second `cargo` process by hand. Every fixture has a matching
+// `.stderr` snapshot; if the compiler's wording drifts, refresh them with
+// `TRYBUILD=overwrite cargo test --test should_fail`.
 #[test]
-fn missing_attributes() {
-    #[derive(Debug, ErrorLevel)]
-    pub enum CustomError {
-        #[level(Warn)]
-        ErrorA,
-        #[level(Info)]
-        ErrorB,
-        ErrorC,
-        ErrorD((String, String)),
-    }
+fn compile_fail() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/compile_fail/*.rs");
 }
@@ -96,3 +96,173 @@ fn with_inner_attribute() {
     assert_eq!(d.error_level(), None);
 }
 
+#[test]
+fn container_default_level() {
+    #[derive(Debug, ErrorLevel)]
+    #[report(info)]
+    pub enum CustomError {
+        #[report(warn)]
+        ErrorA,
+        ErrorB,
+        ErrorC,
+    }
+
+    let a = CustomError::ErrorA;
+    let b = CustomError::ErrorB;
+    let c = CustomError::ErrorC;
+
+    assert_eq!(a.error_level(), Some(Level::Warn));
+    assert_eq!(b.error_level(), Some(Level::Info));
+    assert_eq!(c.error_level(), Some(Level::Info));
+}
+
+#[test]
+fn container_escalate() {
+    #[derive(Debug, ErrorLevel)]
+    pub enum InnerError {
+        #[report(no)]
+        Quiet,
+        #[report(info)]
+        Noisy,
+    }
+
+    #[derive(Debug, ErrorLevel)]
+    #[report(min = "warn")]
+    pub enum CustomError {
+        Wrapped(InnerError),
+    }
+
+    let quiet = CustomError::Wrapped(InnerError::Quiet);
+    let noisy = CustomError::Wrapped(InnerError::Noisy);
+
+    assert_eq!(quiet.error_level(), Some(Level::Warn));
+    assert_eq!(noisy.error_level(), Some(Level::Warn));
+}
+
+#[test]
+fn container_escalate_bare_keyword_still_delegates() {
+    #[derive(Debug, ErrorLevel)]
+    pub enum InnerError {
+        #[report(no)]
+        Quiet,
+        #[report(error)]
+        Severe,
+    }
+
+    #[derive(Debug, ErrorLevel)]
+    #[report(warn, escalate)]
+    pub enum CustomError {
+        Wrapped(InnerError),
+    }
+
+    let quiet = CustomError::Wrapped(InnerError::Quiet);
+    let severe = CustomError::Wrapped(InnerError::Severe);
+
+    // raised up to the floor...
+    assert_eq!(quiet.error_level(), Some(Level::Warn));
+    // ...but never downgraded below it.
+    assert_eq!(severe.error_level(), Some(Level::Error));
+}
+
+#[test]
+fn newtype_struct_with_report() {
+    #[derive(Debug, ErrorLevel)]
+    #[report(error)]
+    pub struct CustomError(String);
+
+    let e = CustomError("oh no".into());
+
+    assert_eq!(e.error_level(), Some(Level::Error));
+}
+
+#[test]
+fn struct_forwards_to_explicit_source() {
+    #[derive(Debug, ErrorLevel)]
+    pub enum InnerError {
+        #[report(warn)]
+        Busted,
+    }
+
+    #[derive(Debug, ErrorLevel)]
+    pub struct CustomError {
+        msg: String,
+        #[source]
+        cause: InnerError,
+    }
+
+    let e = CustomError { msg: "context".into(), cause: InnerError::Busted };
+
+    assert_eq!(e.error_level(), Some(Level::Warn));
+}
+
+#[test]
+fn variant_with_message_and_explicit_source() {
+    #[derive(Debug, ErrorLevel)]
+    pub enum InnerError {
+        #[report(info)]
+        Busted,
+    }
+
+    #[derive(Debug, ErrorLevel)]
+    pub enum CustomError {
+        ErrorA(String, #[source] InnerError),
+    }
+
+    let e = CustomError::ErrorA("context".into(), InnerError::Busted);
+
+    assert_eq!(e.error_level(), Some(Level::Info));
+}
+
+#[test]
+fn log_error_with_fields() {
+    #[derive(Debug, ErrorLevel)]
+    pub enum CustomError {
+        #[report(warn)]
+        Failed { code: u32, path: String },
+    }
+
+    let e = CustomError::Failed { code: 404, path: "/missing".into() };
+
+    println!("this test should output something like: time [WARN] Failed {{ code: 404, path: \"/missing\" }}");
+    e.log_error();
+}
+
+#[test]
+fn log_error_routes_to_target() {
+    #[derive(Debug, ErrorLevel)]
+    pub enum CustomError {
+        #[report(warn, target = "net::tls")]
+        HandshakeFailed,
+        #[report(info)]
+        Retried,
+    }
+
+    let a = CustomError::HandshakeFailed;
+    let b = CustomError::Retried;
+
+    println!("this test should output something like: time [WARN] HandshakeFailed");
+    a.log_error();
+    b.log_error();
+}
+
+#[test]
+fn log_error_with_display() {
+    #[derive(thiserror::Error, Debug, ErrorLevel)]
+    pub enum CustomError {
+        #[report(warn, display)]
+        #[error("connection to {0} refused")]
+        Refused(String),
+        #[report(error)]
+        #[error("timed out")]
+        TimedOut,
+    }
+
+    let a = CustomError::Refused("10.0.0.1".into());
+    let b = CustomError::TimedOut;
+
+    println!("this test should output something like: time [WARN] connection to 10.0.0.1 refused");
+    a.log_error();
+    println!("this test should output something like: time [ERROR] TimedOut");
+    b.log_error();
+}
+
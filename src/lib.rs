@@ -1,5 +1,7 @@
 pub trait ErrorLevel : std::fmt::Debug {
     fn error_level(&self) -> Option<log::Level>;
+
+    #[cfg(feature = "log")]
     fn log_error(&self){
         match self.error_level() {
             None => (),
@@ -10,6 +12,34 @@ pub trait ErrorLevel : std::fmt::Debug {
             Some(log::Level::Error) => log::error!("{:?}", &self),
         }
     }
+
+    /// `tracing` sibling of [`log_error`](Self::log_error): emits a
+    /// `tracing` event at the variant's level instead of a `log` record.
+    /// `tracing::event!` needs its level as a compile-time constant, so
+    /// each arm spells out its own call rather than interpolating one.
+    #[cfg(feature = "tracing")]
+    fn trace_error(&self){
+        match self.error_level() {
+            None => (),
+            Some(log::Level::Trace) => tracing::event!(tracing::Level::TRACE, error = ?self),
+            Some(log::Level::Debug) => tracing::event!(tracing::Level::DEBUG, error = ?self),
+            Some(log::Level::Info) => tracing::event!(tracing::Level::INFO, error = ?self),
+            Some(log::Level::Warn) => tracing::event!(tracing::Level::WARN, error = ?self),
+            Some(log::Level::Error) => tracing::event!(tracing::Level::ERROR, error = ?self),
+        }
+    }
+}
+
+/// Raises `level` to `floor` when it is absent or less severe than the
+/// floor, otherwise leaves it untouched. Used by the derive's
+/// `#[report(escalate)]`/`#[report(min = "...")]` container attribute to
+/// guarantee a minimum severity for variants that forward to an inner
+/// error.
+pub fn escalate(level: Option<log::Level>, floor: log::Level) -> Option<log::Level> {
+    match level {
+        Some(level) if level <= floor => Some(level),
+        _ => Some(floor),
+    }
 }
 
 #[cfg(test)]